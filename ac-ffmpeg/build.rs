@@ -49,7 +49,9 @@ fn main() {
         .file(src_codec_dir.join("bsf.c"))
         .file(src_codec_dir.join("mod.c"))
         .file(src_codec_dir.join("frame.c"))
+        .file(src_codec_dir.join("filter.c"))
         .file(src_codec_audio_dir.join("resampler.c"))
+        .file(src_codec_audio_dir.join("fifo.c"))
         .file(src_codec_video_dir.join("scaler.c"))
         .compile("ffwrapper");
 
@@ -84,6 +86,7 @@ fn main() {
     }
 
     link("avcodec", ffmpeg_link_mode);
+    link("avfilter", ffmpeg_link_mode);
     link("avformat", ffmpeg_link_mode);
     link("avutil", ffmpeg_link_mode);
     link("swresample", ffmpeg_link_mode);
@@ -0,0 +1,103 @@
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::ptr;
+
+use crate::codec::audio::AudioFrame;
+use crate::codec::{AudioCodecParameters, CodecError, ErrorKind};
+use crate::packet::Packet;
+use crate::time::TimeBase;
+use crate::Error;
+
+extern "C" {
+    fn ffw_decoder_new(params: *const c_void) -> *mut c_void;
+    fn ffw_decoder_get_time_base(ptr: *mut c_void, num: *mut c_int, den: *mut c_int);
+    fn ffw_decoder_push_packet(ptr: *mut c_void, packet: *mut c_void) -> std::os::raw::c_int;
+    fn ffw_decoder_take_frame(ptr: *mut c_void, frame: *mut *mut c_void) -> std::os::raw::c_int;
+    fn ffw_decoder_free(ptr: *mut c_void);
+}
+
+/// An audio decoder.
+///
+/// # Usage
+/// 1. Push a packet to the decoder.
+/// 2. Take all frames from the decoder until you get None.
+/// 3. If there are more packets to be decoded, continue with 1.
+/// 4. Flush the decoder.
+/// 5. Take all frames from the decoder until you get None.
+pub struct AudioDecoder {
+    ptr: *mut c_void,
+}
+
+impl AudioDecoder {
+    /// Create and open a decoder matching a given set of codec parameters.
+    pub fn from_codec_parameters(params: &AudioCodecParameters) -> Result<AudioDecoder, Error> {
+        let ptr = unsafe { ffw_decoder_new(params.as_ptr()) };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(ErrorKind::Error, "unable to create the decoder").into());
+        }
+
+        Ok(AudioDecoder { ptr })
+    }
+
+    /// Get the time base frame timestamps coming out of this decoder are
+    /// expressed in, if the decoder reported one. `None` means the decoder
+    /// left its time base unset, in which case callers should fall back to
+    /// their own assumption (e.g. `1 / sample_rate`).
+    pub fn time_base(&self) -> Option<TimeBase> {
+        let mut num = 0;
+        let mut den = 0;
+
+        unsafe { ffw_decoder_get_time_base(self.ptr, &mut num, &mut den) };
+
+        if num == 0 || den == 0 {
+            None
+        } else {
+            Some(TimeBase::new(num, den))
+        }
+    }
+
+    /// Push a given packet to the decoder.
+    pub fn push(&mut self, packet: &Packet) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_decoder_push_packet(self.ptr, packet.as_ptr() as _) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Flush the decoder.
+    pub fn flush(&mut self) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_decoder_push_packet(self.ptr, ptr::null_mut()) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Take the next frame from the decoder.
+    pub fn take(&mut self) -> Result<Option<AudioFrame>, CodecError> {
+        let mut fptr = ptr::null_mut();
+
+        let ret = unsafe { ffw_decoder_take_frame(self.ptr, &mut fptr) };
+
+        match ret {
+            0 if fptr.is_null() => Ok(None),
+            0 => Ok(Some(unsafe { AudioFrame::from_raw_ptr(fptr) })),
+            ret if ret == ErrorKind::Again.into_raw() => Ok(None),
+            ret => Err(CodecError::from_raw_error_code(ret)),
+        }
+    }
+}
+
+impl Drop for AudioDecoder {
+    fn drop(&mut self) {
+        unsafe { ffw_decoder_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for AudioDecoder {}
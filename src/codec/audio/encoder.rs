@@ -0,0 +1,160 @@
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::ptr;
+
+use crate::codec::audio::AudioFrame;
+use crate::codec::{AudioCodecParameters, CodecError, ErrorKind};
+use crate::dictionary::Dictionary;
+use crate::packet::Packet;
+use crate::Error;
+
+extern "C" {
+    fn ffw_encoder_new(params: *const c_void) -> *mut c_void;
+    fn ffw_encoder_set_time_base(ptr: *mut c_void, num: c_int, den: c_int);
+    fn ffw_encoder_open(ptr: *mut c_void, options: *mut *mut c_void) -> c_int;
+    fn ffw_encoder_push_frame(ptr: *mut c_void, frame: *mut c_void) -> c_int;
+    fn ffw_encoder_take_packet(ptr: *mut c_void, packet: *mut *mut c_void) -> c_int;
+    fn ffw_encoder_get_samples_per_frame(ptr: *mut c_void) -> c_int;
+    fn ffw_encoder_get_codec_parameters(ptr: *mut c_void) -> *mut c_void;
+    fn ffw_encoder_free(ptr: *mut c_void);
+}
+
+/// A builder for `AudioEncoder`.
+pub struct AudioEncoderBuilder {
+    ptr: *mut c_void,
+    options: Dictionary,
+}
+
+impl AudioEncoderBuilder {
+    fn new(ptr: *mut c_void) -> Self {
+        AudioEncoderBuilder {
+            ptr,
+            options: Dictionary::new(),
+        }
+    }
+
+    /// Set the time base packets produced by this encoder will be in.
+    pub fn time_base(self, num: u32, den: u32) -> Self {
+        unsafe { ffw_encoder_set_time_base(self.ptr, num as c_int, den as c_int) };
+        self
+    }
+
+    /// Set codec-private options (e.g. `b`, `profile`, `compression_level`)
+    /// to be passed through to `avcodec_open2`.
+    pub fn options(mut self, options: Dictionary) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Open the encoder. Returns the encoder together with whatever
+    /// `options` entries were not recognized by the underlying codec,
+    /// letting a misspelled option key be detected instead of silently
+    /// ignored.
+    pub fn build(mut self) -> Result<(AudioEncoder, Dictionary), Error> {
+        let ret = unsafe { ffw_encoder_open(self.ptr, self.options.as_mut_ptr()) };
+
+        if ret < 0 {
+            return Err(CodecError::from_raw_error_code(ret).into());
+        }
+
+        let ptr = self.ptr;
+        self.ptr = ptr::null_mut();
+
+        Ok((AudioEncoder { ptr }, std::mem::take(&mut self.options)))
+    }
+}
+
+impl Drop for AudioEncoderBuilder {
+    fn drop(&mut self) {
+        unsafe { ffw_encoder_free(self.ptr) }
+    }
+}
+
+/// An audio encoder.
+///
+/// # Usage
+/// 1. Push a frame to the encoder.
+/// 2. Take all packets from the encoder until you get None.
+/// 3. If there are more frames to be encoded, continue with 1.
+/// 4. Flush the encoder.
+/// 5. Take all packets from the encoder until you get None.
+pub struct AudioEncoder {
+    ptr: *mut c_void,
+}
+
+impl AudioEncoder {
+    /// Get a builder for an encoder matching a given set of codec
+    /// parameters (codec, sample format/rate, channel layout, bit rate).
+    pub fn from_codec_parameters(
+        params: &AudioCodecParameters,
+    ) -> Result<AudioEncoderBuilder, Error> {
+        let ptr = unsafe { ffw_encoder_new(params.as_ptr()) };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(ErrorKind::Error, "unable to create the encoder").into());
+        }
+
+        Ok(AudioEncoderBuilder::new(ptr))
+    }
+
+    /// Get the number of samples per frame expected by the encoder, or 0 if
+    /// it accepts frames of any size.
+    pub fn samples_per_frame(&self) -> i32 {
+        unsafe { ffw_encoder_get_samples_per_frame(self.ptr) }
+    }
+
+    /// Get codec parameters of the encoded stream.
+    pub fn codec_parameters(&self) -> AudioCodecParameters {
+        let ptr = unsafe { ffw_encoder_get_codec_parameters(self.ptr) };
+
+        if ptr.is_null() {
+            panic!("unable to get codec parameters from an AudioEncoder");
+        }
+
+        unsafe { AudioCodecParameters::from_raw_ptr(ptr) }
+    }
+
+    /// Push a given frame to the encoder.
+    pub fn push(&mut self, frame: &AudioFrame) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_encoder_push_frame(self.ptr, frame.as_ptr() as _) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Flush the encoder.
+    pub fn flush(&mut self) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_encoder_push_frame(self.ptr, ptr::null_mut()) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Take the next packet from the encoder.
+    pub fn take(&mut self) -> Result<Option<Packet>, CodecError> {
+        let mut pptr = ptr::null_mut();
+
+        let ret = unsafe { ffw_encoder_take_packet(self.ptr, &mut pptr) };
+
+        match ret {
+            0 if pptr.is_null() => Ok(None),
+            0 => Ok(Some(unsafe { Packet::from_raw_ptr(pptr) })),
+            ret if ret == ErrorKind::Again.into_raw() => Ok(None),
+            ret => Err(CodecError::from_raw_error_code(ret)),
+        }
+    }
+}
+
+impl Drop for AudioEncoder {
+    fn drop(&mut self) {
+        unsafe { ffw_encoder_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for AudioEncoder {}
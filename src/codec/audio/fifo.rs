@@ -0,0 +1,109 @@
+use std::ffi::c_void;
+use std::os::raw::c_int;
+
+use crate::codec::audio::AudioFrame;
+use crate::codec::CodecError;
+
+/// `AV_SAMPLE_FMT_FLT`: packed (interleaved) 32-bit float, the only layout
+/// the FIFO is configured for.
+const AV_SAMPLE_FMT_FLT: i32 = 3;
+
+extern "C" {
+    fn ffw_audio_fifo_new(channels: c_int) -> *mut c_void;
+    fn ffw_audio_fifo_write(ptr: *mut c_void, frame: *mut c_void) -> c_int;
+    fn ffw_audio_fifo_read(ptr: *mut c_void, data: *mut f32, nb_samples: c_int) -> c_int;
+    fn ffw_audio_fifo_size(ptr: *mut c_void) -> c_int;
+    fn ffw_audio_fifo_free(ptr: *mut c_void);
+}
+
+/// A FIFO of interleaved `f32` audio samples, backed by `av_audio_fifo_*`.
+///
+/// Decoded/resampled frames are pushed in with `produce` and pulled back out
+/// in caller-chosen chunk sizes with `consume_exact`. This is the shape
+/// real-time playback callbacks (cpal, CoreAudio, ...) expect: the output
+/// device asks for a fixed number of sample frames per callback that rarely
+/// lines up with the decoder's frame size.
+pub struct AudioFifo {
+    ptr: *mut c_void,
+    channels: u32,
+}
+
+impl AudioFifo {
+    /// Create a new FIFO for interleaved `f32` audio with a given number of
+    /// channels.
+    pub fn new(channels: u32) -> AudioFifo {
+        let ptr = unsafe { ffw_audio_fifo_new(channels as c_int) };
+
+        if ptr.is_null() {
+            panic!("unable to allocate an audio FIFO");
+        }
+
+        AudioFifo { ptr, channels }
+    }
+
+    /// Push all samples of a given frame into the FIFO. The frame must
+    /// carry interleaved `f32` samples with the same channel count the FIFO
+    /// was created with (i.e. it should already have gone through a
+    /// resampler/filter graph targeting that format). Panics otherwise,
+    /// rather than silently reinterpreting e.g. planar `FLTP` data as
+    /// interleaved and corrupting the buffer.
+    pub fn produce(&mut self, frame: &AudioFrame) -> Result<(), CodecError> {
+        assert_eq!(
+            frame.raw_sample_format(),
+            AV_SAMPLE_FMT_FLT,
+            "AudioFifo only accepts packed (interleaved) f32 samples; got a planar or \
+             differently-typed frame (resample/filter to AV_SAMPLE_FMT_FLT first)",
+        );
+        assert_eq!(
+            frame.channels(),
+            self.channels,
+            "frame channel count does not match the FIFO's channel count",
+        );
+
+        let ret = unsafe { ffw_audio_fifo_write(self.ptr, frame.as_ptr() as _) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Get the number of sample frames currently buffered in the FIFO.
+    pub fn samples_available(&self) -> usize {
+        unsafe { ffw_audio_fifo_size(self.ptr) as usize }
+    }
+
+    /// Try to fill a given interleaved buffer with exactly
+    /// `buffer.len() / channels` sample frames, advancing the internal read
+    /// cursor across frame boundaries as needed. Returns false (without
+    /// consuming anything) if fewer samples are currently buffered than
+    /// requested.
+    pub fn consume_exact(&mut self, buffer: &mut [f32]) -> bool {
+        let channels = self.channels as usize;
+
+        assert_eq!(
+            buffer.len() % channels,
+            0,
+            "buffer length must be a multiple of the channel count"
+        );
+
+        let requested = buffer.len() / channels;
+
+        if self.samples_available() < requested {
+            return false;
+        }
+
+        let read = unsafe { ffw_audio_fifo_read(self.ptr, buffer.as_mut_ptr(), requested as c_int) };
+
+        read as usize == requested
+    }
+}
+
+impl Drop for AudioFifo {
+    fn drop(&mut self) {
+        unsafe { ffw_audio_fifo_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for AudioFifo {}
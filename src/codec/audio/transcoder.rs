@@ -2,8 +2,12 @@ use std::collections::VecDeque;
 
 use crate::Error;
 
-use crate::codec::audio::{AudioDecoder, AudioEncoder, AudioFrame, AudioResampler};
+use crate::codec::audio::{
+    AudioDecoder, AudioEncoder, AudioFrame, AudioResampler, ChannelLayout, SampleFormat,
+};
+use crate::codec::filter::AudioFilterGraph;
 use crate::codec::{AudioCodecParameters, CodecError, ErrorKind};
+use crate::dictionary::Dictionary;
 use crate::packet::Packet;
 
 /// Audio transcoder.
@@ -15,21 +19,33 @@ use crate::packet::Packet;
 /// 4. Flush the transcoder.
 /// 5. Take all packets from the transcoder until you get None.
 ///
-/// The output timestamp sequence always starts from 0 (note: there still
-/// might be packets with negative timestamps because of the initial padding
-/// of some codecs), timestamps of all output packets are in microseconds.
+/// The output timestamp sequence always starts from 0, timestamps of all
+/// output packets are in microseconds.
 ///
 /// Timestamps of input packets must be set and they are expected to start
-/// from zero (except the initial padding, which will be skipped after
-/// decoding). Time base of the input packets does not matter.
+/// from zero. The initial priming/padding samples added by the encoder that
+/// produced the input are trimmed off sample-accurately after decoding, so
+/// they never reach the output. Time base of the input packets does not
+/// matter.
 pub struct AudioTranscoder {
     audio_decoder: AudioDecoder,
+    audio_filter: Option<AudioFilterGraph>,
     audio_encoder: AudioEncoder,
     audio_resampler: AudioResampler,
 
+    source_channel_layout: ChannelLayout,
+    source_sample_format: SampleFormat,
+    source_sample_rate: u32,
+
     output_sample_rate: u32,
     output_samples: u64,
 
+    skip_samples: u64,
+    leading_skip_resolved: bool,
+    trailing_skip_samples: u64,
+    pending_tail: VecDeque<AudioFrame>,
+    pending_tail_samples: u64,
+
     ready: VecDeque<Packet>,
 }
 
@@ -39,10 +55,26 @@ impl AudioTranscoder {
         input: &AudioCodecParameters,
         output: &AudioCodecParameters,
     ) -> Result<AudioTranscoder, Error> {
+        let (transcoder, _) = AudioTranscoder::with_encoder_options(input, output, None)?;
+
+        Ok(transcoder)
+    }
+
+    /// Create a new transcoder for a given input and output, passing
+    /// codec-private options (e.g. `b`, `profile`, `compression_level`) to
+    /// the encoder. Returns the transcoder together with whatever
+    /// `encoder_options` entries were not recognized by the encoder, so that
+    /// a typo in an option key is detectable instead of silently ignored.
+    pub fn with_encoder_options(
+        input: &AudioCodecParameters,
+        output: &AudioCodecParameters,
+        encoder_options: Option<Dictionary>,
+    ) -> Result<(AudioTranscoder, Dictionary), Error> {
         let decoder = AudioDecoder::from_codec_parameters(input)?;
 
-        let encoder = AudioEncoder::from_codec_parameters(output)?
+        let (encoder, unconsumed_encoder_options) = AudioEncoder::from_codec_parameters(output)?
             .time_base(1, output.sample_rate())
+            .options(encoder_options.unwrap_or_default())
             .build()?;
 
         let resampler = AudioResampler::builder()
@@ -55,18 +87,70 @@ impl AudioTranscoder {
             .target_frame_samples(encoder.samples_per_frame())
             .build()?;
 
+        // initial_padding is reported in terms of the input sample rate,
+        // which is also the rate at which the decoder emits frames, so it
+        // is used as a sample count without rescaling.
+        let skip_samples = u64::from(input.initial_padding());
+
         let res = AudioTranscoder {
             audio_decoder: decoder,
+            audio_filter: None,
             audio_encoder: encoder,
             audio_resampler: resampler,
 
+            source_channel_layout: input.channel_layout(),
+            source_sample_format: input.sample_format(),
+            source_sample_rate: input.sample_rate(),
+
             output_sample_rate: output.sample_rate(),
             output_samples: 0,
 
+            skip_samples,
+            leading_skip_resolved: false,
+            trailing_skip_samples: 0,
+            pending_tail: VecDeque::new(),
+            pending_tail_samples: 0,
+
             ready: VecDeque::new(),
         };
 
-        Ok(res)
+        Ok((res, unconsumed_encoder_options))
+    }
+
+    /// Route decoded frames through an audio filter graph built from a given
+    /// libavfilter filter description before they reach the resampler (e.g.
+    /// `"volume=2.0"`, `"atempo=1.25"`, `"loudnorm"`). This must be called
+    /// right after construction, before any packets are pushed.
+    ///
+    /// The resampler after the graph stays configured for the input's sample
+    /// format, sample rate and channel layout; it is not reconfigured for a
+    /// filter chain whose output differs in any of them (as `loudnorm`,
+    /// which outputs 192 kHz, does).
+    pub fn filter(mut self, spec: &str) -> Result<AudioTranscoder, Error> {
+        let mut builder = AudioFilterGraph::builder()
+            .source_channel_layout(self.source_channel_layout)
+            .source_sample_format(self.source_sample_format)
+            .source_sample_rate(self.source_sample_rate);
+
+        // Match the abuffer source's time base to the one the decoder
+        // actually stamps frame pts with, rather than leaving the graph to
+        // assume `1 / sample_rate`; otherwise pts-sensitive filters like
+        // atempo or loudnorm can misread frame timing.
+        if let Some(time_base) = self.audio_decoder.time_base() {
+            builder = builder.source_time_base(time_base);
+        }
+
+        // When the encoder cannot accept variable-sized frames, make the
+        // filter graph's sink hand back frames that already match it.
+        let frame_size = self.audio_encoder.samples_per_frame();
+
+        if frame_size > 0 {
+            builder = builder.sink_frame_size(frame_size as usize);
+        }
+
+        self.audio_filter = Some(builder.build(spec)?);
+
+        Ok(self)
     }
 
     /// Get codec parameters of the transcoded stream.
@@ -98,6 +182,7 @@ impl AudioTranscoder {
         }
 
         self.flush_decoder()?;
+        self.flush_filter()?;
         self.flush_resampler()?;
         self.flush_encoder()?;
 
@@ -109,22 +194,146 @@ impl AudioTranscoder {
         Ok(self.ready.pop_front())
     }
 
-    /// Push a given packet to the internal decoder, take all decoded frames
-    /// and pass them to the push_to_resampler method.
+    /// Push a given packet to the internal decoder, trim the leading padding
+    /// off the decoded frames and pass the rest to the forward_or_hold
+    /// method.
     fn push_to_decoder(&mut self, packet: &Packet) -> Result<(), CodecError> {
+        // AV_PKT_DATA_SKIP_SAMPLES side data, when present, describes the
+        // same leading encoder priming as `initial_padding` (it is only
+        // meaningful on the first packet of the stream), so it replaces
+        // rather than adds to the `initial_padding`-derived estimate; using
+        // both would trim the head twice. Trailing skip is refreshed from
+        // every packet, since it always describes "if the stream ends after
+        // this packet, trim this many samples off the end".
+        if let Some((skip_start, skip_end)) = packet.skip_samples() {
+            if !self.leading_skip_resolved {
+                self.skip_samples = u64::from(skip_start);
+            }
+
+            self.trailing_skip_samples = u64::from(skip_end);
+        }
+
+        self.leading_skip_resolved = true;
+
         self.audio_decoder.push(packet)?;
 
         while let Some(frame) = self.audio_decoder.take()? {
-            // XXX: this is to skip the initial padding; a correct solution
-            // would be to skip a given number of samples
-            if frame.pts() >= 0 {
-                self.push_to_resampler(frame)?;
+            if let Some(frame) = self.trim_leading_padding(frame) {
+                self.forward_or_hold(frame)?;
             }
         }
 
         Ok(())
     }
 
+    /// Trim the leading priming/padding samples off a freshly decoded frame,
+    /// sample-accurately. Returns None if the whole frame fell within the
+    /// padding region.
+    fn trim_leading_padding(&mut self, frame: AudioFrame) -> Option<AudioFrame> {
+        if self.skip_samples == 0 {
+            return Some(frame);
+        }
+
+        let to_skip = self.skip_samples.min(frame.samples() as u64);
+
+        self.skip_samples -= to_skip;
+
+        if to_skip == frame.samples() as u64 {
+            return None;
+        }
+
+        Some(frame.skip_front(to_skip as usize))
+    }
+
+    /// Hold enough of the most recently decoded (and leading-trimmed)
+    /// frames back to cover `trailing_skip_samples`, so that, once the
+    /// stream actually ends, the trailing padding can be trimmed off the
+    /// true last frames sample-accurately, however many frames it spans,
+    /// rather than just the very last one.
+    fn forward_or_hold(&mut self, frame: AudioFrame) -> Result<(), CodecError> {
+        self.pending_tail_samples += frame.samples() as u64;
+        self.pending_tail.push_back(frame);
+
+        while let Some(front) = self.pending_tail.front() {
+            let front_samples = front.samples() as u64;
+
+            if self.pending_tail_samples - front_samples < self.trailing_skip_samples {
+                break;
+            }
+
+            let front = self.pending_tail.pop_front().unwrap();
+
+            self.pending_tail_samples -= front_samples;
+
+            self.push_to_filter_or_resampler(front)?;
+        }
+
+        Ok(())
+    }
+
+    /// Push a given frame to the audio filter graph, if one was installed
+    /// via `filter`, otherwise push it straight to the resampler.
+    fn push_to_filter_or_resampler(&mut self, frame: AudioFrame) -> Result<(), CodecError> {
+        if self.audio_filter.is_some() {
+            self.push_to_filter(frame)
+        } else {
+            self.push_to_resampler(frame)
+        }
+    }
+
+    /// Push a given frame to the internal filter graph, take all filtered
+    /// frames and pass them to the push_to_resampler method.
+    fn push_to_filter(&mut self, frame: AudioFrame) -> Result<(), CodecError> {
+        let filter = self
+            .audio_filter
+            .as_mut()
+            .expect("push_to_filter called without a filter graph");
+
+        filter.push(&frame)?;
+
+        while let Some(frame) = filter.take()? {
+            self.push_to_resampler(frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Flush the internal filter graph, if any, take all filtered frames and
+    /// pass them to the push_to_resampler method.
+    fn flush_filter(&mut self) -> Result<(), CodecError> {
+        let filter = match self.audio_filter.as_mut() {
+            Some(filter) => filter,
+            None => return Ok(()),
+        };
+
+        filter.flush()?;
+
+        while let Some(frame) = filter.take()? {
+            self.push_to_resampler(frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Trim up to `trailing_skip_samples` off the end of a given frame (the
+    /// last one still held back), sample-accurately. Returns None if the
+    /// whole frame fell within the padding region.
+    fn trim_trailing_padding(&mut self, frame: AudioFrame) -> Option<AudioFrame> {
+        if self.trailing_skip_samples == 0 {
+            return Some(frame);
+        }
+
+        let to_skip = self.trailing_skip_samples.min(frame.samples() as u64);
+
+        self.trailing_skip_samples -= to_skip;
+
+        if to_skip == frame.samples() as u64 {
+            return None;
+        }
+
+        Some(frame.skip_back(to_skip as usize))
+    }
+
     /// Push a given frame to the internal resampler, take all resampled frames
     /// and pass them to the push_to_encoder method.
     fn push_to_resampler(&mut self, frame: AudioFrame) -> Result<(), CodecError> {
@@ -162,13 +371,47 @@ impl AudioTranscoder {
         self.ready.push_back(packet);
     }
 
-    /// Flush the internal decoder, take all decoded frames and pass them to
-    /// the push_to_resampler method.
+    /// Flush the internal decoder, trim the trailing padding off the last
+    /// decoded frame(s) and pass the rest to the push_to_filter_or_resampler
+    /// method.
     fn flush_decoder(&mut self) -> Result<(), CodecError> {
         self.audio_decoder.flush()?;
 
         while let Some(frame) = self.audio_decoder.take()? {
-            self.push_to_resampler(frame)?;
+            if let Some(frame) = self.trim_leading_padding(frame) {
+                self.forward_or_hold(frame)?;
+            }
+        }
+
+        self.finalize_tail()?;
+
+        Ok(())
+    }
+
+    /// Trim `trailing_skip_samples` off the very end of the held-back tail
+    /// (working backwards, since the padding may span more than one
+    /// frame) and forward whatever is left, in order.
+    fn finalize_tail(&mut self) -> Result<(), CodecError> {
+        let mut trimmed_end = VecDeque::new();
+
+        while let Some(frame) = self.pending_tail.pop_back() {
+            match self.trim_trailing_padding(frame) {
+                Some(frame) => {
+                    trimmed_end.push_front(frame);
+                    break;
+                }
+                None => continue,
+            }
+        }
+
+        while let Some(frame) = self.pending_tail.pop_back() {
+            trimmed_end.push_front(frame);
+        }
+
+        self.pending_tail_samples = 0;
+
+        for frame in trimmed_end {
+            self.push_to_filter_or_resampler(frame)?;
         }
 
         Ok(())
@@ -0,0 +1,192 @@
+use std::ffi::{c_void, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use crate::codec::audio::{ChannelLayout, SampleFormat};
+use crate::codec::{AudioFrame, CodecError, ErrorKind};
+use crate::time::TimeBase;
+
+extern "C" {
+    fn ffw_filtergraph_new(
+        filters: *const c_char,
+        source_sample_rate: c_int,
+        source_sample_format: c_int,
+        source_channel_layout: u64,
+        source_time_base_num: c_int,
+        source_time_base_den: c_int,
+        sink_frame_size: c_int,
+    ) -> *mut c_void;
+
+    fn ffw_filtergraph_push_frame(ptr: *mut c_void, frame: *mut c_void) -> c_int;
+    fn ffw_filtergraph_take_frame(ptr: *mut c_void, frame: *mut *mut c_void) -> c_int;
+    fn ffw_filtergraph_free(ptr: *mut c_void);
+}
+
+/// A builder for `AudioFilterGraph`.
+pub struct AudioFilterGraphBuilder {
+    source_channel_layout: Option<ChannelLayout>,
+    source_sample_format: Option<SampleFormat>,
+    source_sample_rate: Option<u32>,
+    source_time_base: Option<TimeBase>,
+    sink_frame_size: Option<usize>,
+    filters: Option<String>,
+}
+
+impl AudioFilterGraphBuilder {
+    fn new() -> Self {
+        AudioFilterGraphBuilder {
+            source_channel_layout: None,
+            source_sample_format: None,
+            source_sample_rate: None,
+            source_time_base: None,
+            sink_frame_size: None,
+            filters: None,
+        }
+    }
+
+    /// Set the channel layout of the frames that will be pushed into the
+    /// graph.
+    pub fn source_channel_layout(mut self, channel_layout: ChannelLayout) -> Self {
+        self.source_channel_layout = Some(channel_layout);
+        self
+    }
+
+    /// Set the sample format of the frames that will be pushed into the
+    /// graph.
+    pub fn source_sample_format(mut self, sample_format: SampleFormat) -> Self {
+        self.source_sample_format = Some(sample_format);
+        self
+    }
+
+    /// Set the sample rate of the frames that will be pushed into the
+    /// graph.
+    pub fn source_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.source_sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// Set the time base of the frames that will be pushed into the graph.
+    pub fn source_time_base(mut self, time_base: TimeBase) -> Self {
+        self.source_time_base = Some(time_base);
+        self
+    }
+
+    /// Force a fixed number of samples per output frame (e.g. to match an
+    /// encoder that does not support variable frame size). By default the
+    /// sink yields whatever frame size the filter chain produces.
+    pub fn sink_frame_size(mut self, samples: usize) -> Self {
+        self.sink_frame_size = Some(samples);
+        self
+    }
+
+    /// Build the graph from a libavfilter filter description (the same
+    /// syntax accepted by the ffmpeg CLI `-af`/`-filter:a` options, e.g.
+    /// `"volume=2.0,atempo=1.25"`).
+    pub fn build(self, filters: &str) -> Result<AudioFilterGraph, CodecError> {
+        let source_channel_layout = self
+            .source_channel_layout
+            .ok_or_else(|| CodecError::new(ErrorKind::Error, "source channel layout not set"))?;
+
+        let source_sample_format = self
+            .source_sample_format
+            .ok_or_else(|| CodecError::new(ErrorKind::Error, "source sample format not set"))?;
+
+        let source_sample_rate = self
+            .source_sample_rate
+            .ok_or_else(|| CodecError::new(ErrorKind::Error, "source sample rate not set"))?;
+
+        let source_time_base = self.source_time_base.unwrap_or_else(|| TimeBase::new(1, source_sample_rate as i32));
+
+        let filters = CString::new(filters)
+            .map_err(|_| CodecError::new(ErrorKind::Error, "filter description contains a nul byte"))?;
+
+        let ptr = unsafe {
+            ffw_filtergraph_new(
+                filters.as_ptr(),
+                source_sample_rate as c_int,
+                source_sample_format.into_raw(),
+                source_channel_layout.into_raw(),
+                source_time_base.num() as c_int,
+                source_time_base.den() as c_int,
+                self.sink_frame_size.unwrap_or(0) as c_int,
+            )
+        };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(
+                ErrorKind::Error,
+                "unable to build the filter graph (invalid filter description?)",
+            ));
+        }
+
+        Ok(AudioFilterGraph { ptr })
+    }
+}
+
+/// An audio filter-graph built on top of libavfilter.
+///
+/// The graph is fed with an `abuffer` source and read back from an
+/// `abuffersink`, letting arbitrary libavfilter filter chains (`volume`,
+/// `atempo`, `loudnorm`, `aresample`, channel remixing, ...) be inserted
+/// between a decoder and an encoder.
+///
+/// # Usage
+/// 1. Push a frame with the source parameters into the graph.
+/// 2. Take all frames from the graph until you get None.
+/// 3. If there are more frames to be filtered, continue with 1.
+/// 4. Flush the graph.
+/// 5. Take all frames from the graph until you get None.
+pub struct AudioFilterGraph {
+    ptr: *mut c_void,
+}
+
+impl AudioFilterGraph {
+    /// Get a builder for the audio filter graph.
+    pub fn builder() -> AudioFilterGraphBuilder {
+        AudioFilterGraphBuilder::new()
+    }
+
+    /// Push a given frame to the graph.
+    pub fn push(&mut self, frame: &AudioFrame) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_filtergraph_push_frame(self.ptr, frame.as_ptr() as _) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Flush the graph.
+    pub fn flush(&mut self) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_filtergraph_push_frame(self.ptr, ptr::null_mut()) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Take the next frame from the graph.
+    pub fn take(&mut self) -> Result<Option<AudioFrame>, CodecError> {
+        let mut fptr = ptr::null_mut();
+
+        let ret = unsafe { ffw_filtergraph_take_frame(self.ptr, &mut fptr) };
+
+        match ret {
+            0 if fptr.is_null() => Ok(None),
+            0 => Ok(Some(unsafe { AudioFrame::from_raw_ptr(fptr) })),
+            ret if ret == ErrorKind::Again.into_raw() => Ok(None),
+            ret => Err(CodecError::from_raw_error_code(ret)),
+        }
+    }
+}
+
+impl Drop for AudioFilterGraph {
+    fn drop(&mut self) {
+        unsafe { ffw_filtergraph_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for AudioFilterGraph {}
@@ -0,0 +1,114 @@
+use std::ffi::c_void;
+use std::os::raw::c_int;
+
+extern "C" {
+    fn ffw_frame_get_pts(ptr: *mut c_void) -> i64;
+    fn ffw_frame_set_pts(ptr: *mut c_void, pts: i64);
+    fn ffw_frame_get_samples(ptr: *mut c_void) -> c_int;
+    fn ffw_frame_get_channels(ptr: *mut c_void) -> c_int;
+    fn ffw_frame_get_sample_format(ptr: *mut c_void) -> c_int;
+    fn ffw_frame_skip_front(ptr: *mut c_void, count: c_int) -> *mut c_void;
+    fn ffw_frame_skip_back(ptr: *mut c_void, count: c_int) -> *mut c_void;
+    fn ffw_frame_clone(ptr: *mut c_void) -> *mut c_void;
+    fn ffw_frame_free(ptr: *mut c_void);
+}
+
+/// A decoded audio frame (interleaved or planar samples, depending on the
+/// codec/filter graph that produced it).
+pub struct AudioFrame {
+    ptr: *mut c_void,
+}
+
+impl AudioFrame {
+    /// Wrap a raw `AVFrame*` handed back by a decoder/filter graph/resampler,
+    /// taking ownership of it.
+    pub(crate) unsafe fn from_raw_ptr(ptr: *mut c_void) -> AudioFrame {
+        AudioFrame { ptr }
+    }
+
+    /// Get the raw `AVFrame*` for use by an encoder/filter graph/resampler.
+    pub(crate) fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+
+    /// Get the number of samples in the frame (per channel).
+    pub fn samples(&self) -> i32 {
+        unsafe { ffw_frame_get_samples(self.ptr) }
+    }
+
+    /// Get the number of channels.
+    pub fn channels(&self) -> u32 {
+        unsafe { ffw_frame_get_channels(self.ptr) as u32 }
+    }
+
+    /// Get the raw `AVSampleFormat` of the frame's samples (e.g.
+    /// `AV_SAMPLE_FMT_FLT`/`AV_SAMPLE_FMT_FLTP`).
+    pub(crate) fn raw_sample_format(&self) -> i32 {
+        unsafe { ffw_frame_get_sample_format(self.ptr) }
+    }
+
+    /// Get the presentation timestamp.
+    pub fn pts(&self) -> i64 {
+        unsafe { ffw_frame_get_pts(self.ptr) }
+    }
+
+    /// Set the presentation timestamp.
+    pub fn with_pts(self, pts: i64) -> AudioFrame {
+        unsafe { ffw_frame_set_pts(self.ptr, pts) };
+        self
+    }
+
+    /// Get a view of this frame with the first `count` samples skipped: the
+    /// returned frame shares the same underlying sample buffer, with its
+    /// data pointers advanced by `count` samples and its pts shifted
+    /// forward to match. Used to trim encoder priming/padding off the head
+    /// of a decoded stream without copying sample data.
+    ///
+    /// `count` must be less than `self.samples()`.
+    pub fn skip_front(self, count: usize) -> AudioFrame {
+        let ptr = unsafe { ffw_frame_skip_front(self.ptr, count as c_int) };
+
+        if ptr.is_null() {
+            panic!("unable to skip samples off the front of an AudioFrame");
+        }
+
+        AudioFrame { ptr }
+    }
+
+    /// Get a view of this frame with the last `count` samples dropped: the
+    /// returned frame shares the same underlying sample buffer, with its
+    /// sample count reduced (the pts is unaffected). Used to trim encoder
+    /// padding off the tail of a decoded stream without copying sample
+    /// data.
+    ///
+    /// `count` must be less than `self.samples()`.
+    pub fn skip_back(self, count: usize) -> AudioFrame {
+        let ptr = unsafe { ffw_frame_skip_back(self.ptr, count as c_int) };
+
+        if ptr.is_null() {
+            panic!("unable to skip samples off the back of an AudioFrame");
+        }
+
+        AudioFrame { ptr }
+    }
+}
+
+impl Clone for AudioFrame {
+    fn clone(&self) -> AudioFrame {
+        let ptr = unsafe { ffw_frame_clone(self.ptr) };
+
+        if ptr.is_null() {
+            panic!("unable to clone an AudioFrame");
+        }
+
+        AudioFrame { ptr }
+    }
+}
+
+impl Drop for AudioFrame {
+    fn drop(&mut self) {
+        unsafe { ffw_frame_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for AudioFrame {}
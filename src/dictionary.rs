@@ -0,0 +1,172 @@
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_int;
+use std::ptr;
+
+extern "C" {
+    fn av_dict_set(
+        pdict: *mut *mut c_void,
+        key: *const i8,
+        value: *const i8,
+        flags: c_int,
+    ) -> c_int;
+    fn av_dict_get(
+        dict: *const c_void,
+        key: *const i8,
+        prev: *const c_void,
+        flags: c_int,
+    ) -> *mut c_void;
+    fn av_dict_count(dict: *const c_void) -> c_int;
+    fn av_dict_copy(dst: *mut *mut c_void, src: *const c_void, flags: c_int) -> c_int;
+    fn av_dict_free(pdict: *mut *mut c_void);
+}
+
+#[repr(C)]
+struct AVDictionaryEntry {
+    key: *mut i8,
+    value: *mut i8,
+}
+
+/// A key-value map of codec-private options, backed by FFmpeg's
+/// `AVDictionary`.
+///
+/// Used to pass codec-specific options (bitrate mode, `aac_coder`,
+/// `profile`, `compression_level`, ...) through to `avcodec_open2` when
+/// constructing an `AudioEncoder`. `AudioDecoder` takes no dictionary and
+/// opens its codec without codec-private options. Any entries still present
+/// after the codec was opened were not recognized by it, which makes
+/// misspelled option names detectable instead of silently ignored.
+///
+/// # Examples
+/// ```ignore
+/// let mut options = Dictionary::new();
+/// options.set("b", "128k")?;
+/// options.set("profile", "aac_low")?;
+///
+/// let (encoder, unconsumed) = AudioEncoder::from_codec_parameters(&params)?
+///     .options(options)
+///     .build()?;
+///
+/// assert!(unconsumed.is_empty(), "unrecognized encoder option");
+/// ```
+pub struct Dictionary {
+    ptr: *mut c_void,
+}
+
+impl Dictionary {
+    /// Create an empty dictionary.
+    pub fn new() -> Dictionary {
+        Dictionary {
+            ptr: ptr::null_mut(),
+        }
+    }
+
+    /// Set a given key to a given value, overwriting any existing value for
+    /// that key.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), DictionaryError> {
+        let key = CString::new(key).map_err(|_| DictionaryError::NulByte)?;
+        let value = CString::new(value).map_err(|_| DictionaryError::NulByte)?;
+
+        let ret = unsafe { av_dict_set(&mut self.ptr, key.as_ptr(), value.as_ptr(), 0) };
+
+        if ret < 0 {
+            Err(DictionaryError::SetFailed(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Get the value of a given key, if present.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let key = CString::new(key).ok()?;
+
+        let entry = unsafe { av_dict_get(self.ptr, key.as_ptr(), ptr::null(), 0) };
+
+        if entry.is_null() {
+            return None;
+        }
+
+        let entry = entry as *mut AVDictionaryEntry;
+
+        let value = unsafe { CStr::from_ptr((*entry).value) };
+
+        Some(value.to_string_lossy().into_owned())
+    }
+
+    /// Get the number of entries currently in the dictionary.
+    pub fn len(&self) -> usize {
+        unsafe { av_dict_count(self.ptr) as usize }
+    }
+
+    /// Check whether the dictionary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the raw pointer-to-pointer to hand to an FFmpeg function that
+    /// mutates the dictionary in place (e.g. `avcodec_open2`), consuming
+    /// recognized entries and leaving the rest behind.
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut *mut c_void {
+        &mut self.ptr
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Dictionary {
+        Dictionary::new()
+    }
+}
+
+impl Clone for Dictionary {
+    fn clone(&self) -> Dictionary {
+        let mut copy = Dictionary::new();
+
+        let ret = unsafe { av_dict_copy(&mut copy.ptr, self.ptr, 0) };
+
+        if ret < 0 {
+            panic!("unable to clone a Dictionary");
+        }
+
+        copy
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a str)> for Dictionary {
+    fn from_iter<I: IntoIterator<Item = (&'a str, &'a str)>>(iter: I) -> Dictionary {
+        let mut dict = Dictionary::new();
+
+        for (key, value) in iter {
+            dict.set(key, value)
+                .expect("dictionary key/value must not contain a nul byte");
+        }
+
+        dict
+    }
+}
+
+impl Drop for Dictionary {
+    fn drop(&mut self) {
+        unsafe { av_dict_free(&mut self.ptr) }
+    }
+}
+
+unsafe impl Send for Dictionary {}
+
+/// An error that can occur while manipulating a `Dictionary`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DictionaryError {
+    /// A key or value contained an interior nul byte.
+    NulByte,
+    /// The underlying `av_dict_set` call failed with a given error code.
+    SetFailed(i32),
+}
+
+impl std::fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DictionaryError::NulByte => write!(f, "key or value contains a nul byte"),
+            DictionaryError::SetFailed(code) => write!(f, "unable to set a dictionary entry ({})", code),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {}
@@ -0,0 +1,84 @@
+use std::ffi::{c_void, CString};
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+
+use crate::codec::{CodecError, ErrorKind};
+use crate::format::io::IOContext;
+use crate::packet::Packet;
+use crate::Error;
+
+extern "C" {
+    fn ffw_demuxer_open(url: *const i8, io: *mut c_void) -> *mut c_void;
+    fn ffw_demuxer_read_frame(ptr: *mut c_void, packet: *mut *mut c_void) -> c_int;
+    fn ffw_demuxer_free(ptr: *mut c_void);
+}
+
+/// Reads packets out of a container format (file, network stream, ...).
+pub struct Demuxer {
+    ptr: *mut c_void,
+    // Kept alive for as long as the demuxer when it was opened with
+    // `open_io`; `ffw_demuxer_open` sets AVFMT_FLAG_CUSTOM_IO in that case
+    // so `avformat_close_input` leaves the AVIOContext alone, and this field
+    // remains the sole owner, freeing it on drop (after `ptr` is closed).
+    _io: Option<IOContext>,
+}
+
+impl Demuxer {
+    /// Open a demuxer for a given input URL/file path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Demuxer, Error> {
+        let url = path.as_ref().to_string_lossy();
+
+        let url = CString::new(url.as_bytes())
+            .map_err(|_| CodecError::new(ErrorKind::Error, "path contains a nul byte"))?;
+
+        let ptr = unsafe { ffw_demuxer_open(url.as_ptr(), ptr::null_mut()) };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(ErrorKind::Error, "unable to open the input").into());
+        }
+
+        Ok(Demuxer { ptr, _io: None })
+    }
+
+    /// Open a demuxer backed by a custom Rust `Read`/`Read + Seek`
+    /// implementation instead of a file path (see
+    /// `format::io::IOContext::from_reader`/`from_seekable_reader`).
+    pub fn open_io(io: IOContext) -> Result<Demuxer, Error> {
+        let ptr = unsafe { ffw_demuxer_open(ptr::null(), io.as_ptr()) };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(ErrorKind::Error, "unable to open the input").into());
+        }
+
+        Ok(Demuxer {
+            ptr,
+            _io: Some(io),
+        })
+    }
+
+    /// Read the next packet from the demuxer, or `None` once the input is
+    /// exhausted.
+    pub fn read_frame(&mut self) -> Result<Option<Packet>, CodecError> {
+        let mut pptr = ptr::null_mut();
+
+        let ret = unsafe { ffw_demuxer_read_frame(self.ptr, &mut pptr) };
+
+        match ret {
+            0 if pptr.is_null() => Ok(None),
+            0 => Ok(Some(unsafe { Packet::from_raw_ptr(pptr) })),
+            ret => Err(CodecError::from_raw_error_code(ret)),
+        }
+    }
+}
+
+impl Drop for Demuxer {
+    fn drop(&mut self) {
+        // Closes the AVFormatContext first; `_io` (if any) is dropped
+        // afterwards by the compiler, once FFmpeg is done with the custom
+        // AVIOContext it was backing.
+        unsafe { ffw_demuxer_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for Demuxer {}
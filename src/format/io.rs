@@ -0,0 +1,216 @@
+use std::ffi::c_void;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::raw::{c_int, c_longlong};
+use std::slice;
+
+const AVERROR_EOF: c_int = -541478725;
+const AVERROR_EXTERNAL: c_int = -542398533;
+const AVSEEK_SIZE: c_int = 0x10000;
+
+type ReadPacketFn = unsafe extern "C" fn(opaque: *mut c_void, buf: *mut u8, buf_size: c_int) -> c_int;
+type WritePacketFn =
+    unsafe extern "C" fn(opaque: *mut c_void, buf: *const u8, buf_size: c_int) -> c_int;
+type SeekFn = unsafe extern "C" fn(opaque: *mut c_void, offset: c_longlong, whence: c_int) -> c_longlong;
+
+extern "C" {
+    fn ffw_io_context_new(
+        buffer_size: c_int,
+        write_flag: c_int,
+        opaque: *mut c_void,
+        read_packet: Option<ReadPacketFn>,
+        write_packet: Option<WritePacketFn>,
+        seek: Option<SeekFn>,
+    ) -> *mut c_void;
+
+    fn ffw_io_context_free(ptr: *mut c_void);
+}
+
+/// A custom AVIO context that reads from or writes to an arbitrary Rust
+/// `Read`/`Write` implementation instead of a file path or a fixed buffer.
+///
+/// Build one with `from_reader`/`from_seekable_reader` to back a `Demuxer`,
+/// or `from_writer`/`from_seekable_writer` to back a `Muxer`. The
+/// non-seekable constructors install no seek callback at all (not even one
+/// answering `AVSEEK_SIZE`), putting the context into streaming mode so
+/// that libavformat never attempts to seek or probe the size of inputs
+/// arriving from a channel or socket that has none to offer.
+pub struct IOContext {
+    ptr: *mut c_void,
+    opaque: *mut c_void,
+    drop_opaque: unsafe fn(*mut c_void),
+}
+
+impl IOContext {
+    /// Back a `Demuxer` with a non-seekable reader (streaming mode).
+    pub fn from_reader<T>(reader: T, buffer_size: usize) -> IOContext
+    where
+        T: Read + Send + 'static,
+    {
+        Self::new(reader, buffer_size, false, Some(read_trampoline::<T>), None, None)
+    }
+
+    /// Back a `Demuxer` with a seekable reader.
+    pub fn from_seekable_reader<T>(reader: T, buffer_size: usize) -> IOContext
+    where
+        T: Read + Seek + Send + 'static,
+    {
+        Self::new(
+            reader,
+            buffer_size,
+            false,
+            Some(read_trampoline::<T>),
+            None,
+            Some(seek_trampoline::<T>),
+        )
+    }
+
+    /// Back a `Muxer` with a non-seekable writer (streaming mode, e.g. a
+    /// network sink).
+    pub fn from_writer<T>(writer: T, buffer_size: usize) -> IOContext
+    where
+        T: Write + Send + 'static,
+    {
+        Self::new(writer, buffer_size, true, None, Some(write_trampoline::<T>), None)
+    }
+
+    /// Back a `Muxer` with a seekable writer.
+    pub fn from_seekable_writer<T>(writer: T, buffer_size: usize) -> IOContext
+    where
+        T: Write + Seek + Send + 'static,
+    {
+        Self::new(
+            writer,
+            buffer_size,
+            true,
+            None,
+            Some(write_trampoline::<T>),
+            Some(seek_trampoline::<T>),
+        )
+    }
+
+    fn new<T>(
+        inner: T,
+        buffer_size: usize,
+        write_flag: bool,
+        read_packet: Option<ReadPacketFn>,
+        write_packet: Option<WritePacketFn>,
+        seek: Option<SeekFn>,
+    ) -> IOContext
+    where
+        T: Send + 'static,
+    {
+        let opaque = Box::into_raw(Box::new(inner)) as *mut c_void;
+
+        let ptr = unsafe {
+            ffw_io_context_new(
+                buffer_size as c_int,
+                write_flag as c_int,
+                opaque,
+                read_packet,
+                write_packet,
+                seek,
+            )
+        };
+
+        if ptr.is_null() {
+            // SAFETY: opaque was just created from a Box<T> above and has
+            // not been handed to FFmpeg (allocation failed before that).
+            unsafe { drop(Box::from_raw(opaque as *mut T)) };
+
+            panic!("unable to allocate an AVIO context");
+        }
+
+        IOContext {
+            ptr,
+            opaque,
+            drop_opaque: drop_box::<T>,
+        }
+    }
+
+    /// Get the underlying AVIOContext pointer for use by a `Demuxer`/`Muxer`.
+    pub(crate) fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+}
+
+impl Drop for IOContext {
+    fn drop(&mut self) {
+        unsafe {
+            ffw_io_context_free(self.ptr);
+
+            (self.drop_opaque)(self.opaque);
+        }
+    }
+}
+
+unsafe impl Send for IOContext {}
+
+/// Drop the boxed opaque value of a given type behind a type-erased
+/// pointer.
+unsafe fn drop_box<T>(ptr: *mut c_void) {
+    drop(Box::from_raw(ptr as *mut T));
+}
+
+unsafe extern "C" fn read_trampoline<T>(opaque: *mut c_void, buf: *mut u8, buf_size: c_int) -> c_int
+where
+    T: Read,
+{
+    let reader = &mut *(opaque as *mut T);
+    let buf = slice::from_raw_parts_mut(buf, buf_size.max(0) as usize);
+
+    match reader.read(buf) {
+        Ok(0) => AVERROR_EOF,
+        Ok(n) => n as c_int,
+        Err(_) => AVERROR_EXTERNAL,
+    }
+}
+
+unsafe extern "C" fn write_trampoline<T>(
+    opaque: *mut c_void,
+    buf: *const u8,
+    buf_size: c_int,
+) -> c_int
+where
+    T: Write,
+{
+    let writer = &mut *(opaque as *mut T);
+    let buf = slice::from_raw_parts(buf, buf_size.max(0) as usize);
+
+    match writer.write_all(buf) {
+        Ok(()) => buf_size,
+        Err(_) => AVERROR_EXTERNAL,
+    }
+}
+
+unsafe extern "C" fn seek_trampoline<T>(
+    opaque: *mut c_void,
+    offset: c_longlong,
+    whence: c_int,
+) -> c_longlong
+where
+    T: Seek,
+{
+    let stream = &mut *(opaque as *mut T);
+
+    let pos = match whence {
+        0 => SeekFrom::Start(offset as u64),
+        1 => SeekFrom::Current(offset),
+        2 => SeekFrom::End(offset),
+        w if w == AVSEEK_SIZE => {
+            return match stream.seek(SeekFrom::Current(0)).and_then(|original| {
+                let end = stream.seek(SeekFrom::End(0))?;
+                stream.seek(SeekFrom::Start(original))?;
+                Ok(end)
+            }) {
+                Ok(size) => size as c_longlong,
+                Err(_) => -1,
+            };
+        }
+        _ => return -1,
+    };
+
+    match stream.seek(pos) {
+        Ok(offset) => offset as c_longlong,
+        Err(_) => -1,
+    }
+}
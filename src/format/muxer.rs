@@ -0,0 +1,116 @@
+use std::ffi::{c_void, CString};
+use std::os::raw::c_int;
+use std::path::Path;
+use std::ptr;
+
+use crate::codec::{CodecError, ErrorKind};
+use crate::format::io::IOContext;
+use crate::packet::Packet;
+use crate::Error;
+
+extern "C" {
+    fn ffw_muxer_open(format_name: *const i8, url: *const i8, io: *mut c_void) -> *mut c_void;
+    fn ffw_muxer_write_header(ptr: *mut c_void) -> c_int;
+    fn ffw_muxer_write_frame(ptr: *mut c_void, packet: *mut c_void) -> c_int;
+    fn ffw_muxer_write_trailer(ptr: *mut c_void) -> c_int;
+    fn ffw_muxer_free(ptr: *mut c_void);
+}
+
+/// Writes packets into a container format (file, network stream, ...).
+pub struct Muxer {
+    ptr: *mut c_void,
+    // Kept alive for as long as the muxer when it was opened with
+    // `create_io`; `ffw_muxer_free` respects AVFMT_FLAG_CUSTOM_IO in that
+    // case so it leaves the AVIOContext alone, and this field remains the
+    // sole owner, freeing it on drop (after `ptr` is closed).
+    _io: Option<IOContext>,
+}
+
+impl Muxer {
+    /// Create a muxer for a given short format name (e.g. `"mp4"`,
+    /// `"matroska"`) writing to a given output URL/file path.
+    pub fn create<P: AsRef<Path>>(format_name: &str, path: P) -> Result<Muxer, Error> {
+        let format_name = CString::new(format_name)
+            .map_err(|_| CodecError::new(ErrorKind::Error, "format name contains a nul byte"))?;
+
+        let url = path.as_ref().to_string_lossy();
+        let url = CString::new(url.as_bytes())
+            .map_err(|_| CodecError::new(ErrorKind::Error, "path contains a nul byte"))?;
+
+        let ptr = unsafe { ffw_muxer_open(format_name.as_ptr(), url.as_ptr(), ptr::null_mut()) };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(ErrorKind::Error, "unable to open the output").into());
+        }
+
+        Ok(Muxer { ptr, _io: None })
+    }
+
+    /// Create a muxer for a given short format name, writing to a custom
+    /// Rust `Write`/`Write + Seek` implementation instead of a file path
+    /// (see `format::io::IOContext::from_writer`/`from_seekable_writer`).
+    pub fn create_io(format_name: &str, io: IOContext) -> Result<Muxer, Error> {
+        let format_name = CString::new(format_name)
+            .map_err(|_| CodecError::new(ErrorKind::Error, "format name contains a nul byte"))?;
+
+        let ptr = unsafe { ffw_muxer_open(format_name.as_ptr(), ptr::null(), io.as_ptr()) };
+
+        if ptr.is_null() {
+            return Err(CodecError::new(ErrorKind::Error, "unable to open the output").into());
+        }
+
+        Ok(Muxer {
+            ptr,
+            _io: Some(io),
+        })
+    }
+
+    /// Write the container header. Must be called exactly once, before any
+    /// call to `write_frame`.
+    pub fn write_header(&mut self) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_muxer_write_header(self.ptr) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write a given packet. Takes ownership of the packet:
+    /// `av_interleaved_write_frame` unreferences its buffers internally, and
+    /// the (now empty) `Packet` is then dropped normally, freeing the
+    /// underlying `AVPacket` struct.
+    pub fn write_frame(&mut self, packet: Packet) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_muxer_write_frame(self.ptr, packet.as_ptr()) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write the container trailer. Must be called exactly once, after all
+    /// packets have been written.
+    pub fn write_trailer(&mut self) -> Result<(), CodecError> {
+        let ret = unsafe { ffw_muxer_write_trailer(self.ptr) };
+
+        if ret < 0 {
+            Err(CodecError::from_raw_error_code(ret))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for Muxer {
+    fn drop(&mut self) {
+        // Closes the AVFormatContext first; `_io` (if any) is dropped
+        // afterwards by the compiler, once FFmpeg is done with the custom
+        // AVIOContext it was backing.
+        unsafe { ffw_muxer_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for Muxer {}
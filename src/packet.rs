@@ -0,0 +1,112 @@
+use std::ffi::c_void;
+use std::os::raw::c_int;
+
+extern "C" {
+    fn ffw_packet_get_side_data(
+        ptr: *mut c_void,
+        kind: c_int,
+        size: *mut c_int,
+    ) -> *const u8;
+
+    fn ffw_packet_pts(ptr: *mut c_void) -> i64;
+    fn ffw_packet_dts(ptr: *mut c_void) -> i64;
+    fn ffw_packet_set_pts(ptr: *mut c_void, pts: i64);
+    fn ffw_packet_set_dts(ptr: *mut c_void, dts: i64);
+    fn ffw_packet_set_stream_index(ptr: *mut c_void, index: c_int);
+    fn ffw_packet_clone(ptr: *mut c_void) -> *mut c_void;
+    fn ffw_packet_free(ptr: *mut c_void);
+}
+
+/// AVPacketSideData type carrying the number of samples to skip from the
+/// start/end of the audio obtained by decoding a given packet.
+const AV_PKT_DATA_SKIP_SAMPLES: c_int = 11;
+
+/// A single compressed packet, as read from/written to a container format.
+pub struct Packet {
+    ptr: *mut c_void,
+}
+
+impl Packet {
+    /// Wrap a raw `AVPacket*` handed back by a decoder/demuxer, taking
+    /// ownership of it.
+    pub(crate) unsafe fn from_raw_ptr(ptr: *mut c_void) -> Packet {
+        Packet { ptr }
+    }
+
+    /// Get the raw `AVPacket*` for use by an encoder/muxer.
+    pub(crate) fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+
+    /// Get the presentation timestamp.
+    pub fn pts(&self) -> i64 {
+        unsafe { ffw_packet_pts(self.ptr) }
+    }
+
+    /// Get the decoding timestamp.
+    pub fn dts(&self) -> i64 {
+        unsafe { ffw_packet_dts(self.ptr) }
+    }
+
+    /// Set the presentation timestamp.
+    pub fn with_pts(self, pts: i64) -> Packet {
+        unsafe { ffw_packet_set_pts(self.ptr, pts) };
+        self
+    }
+
+    /// Set the decoding timestamp.
+    pub fn with_dts(self, dts: i64) -> Packet {
+        unsafe { ffw_packet_set_dts(self.ptr, dts) };
+        self
+    }
+
+    /// Set the index of the stream this packet belongs to.
+    pub fn with_stream_index(self, index: u32) -> Packet {
+        unsafe { ffw_packet_set_stream_index(self.ptr, index as c_int) };
+        self
+    }
+
+    /// Read the `AV_PKT_DATA_SKIP_SAMPLES` side data, if present: the
+    /// number of samples to be discarded from the start and from the end
+    /// of the decoded frame(s), respectively. Some demuxers (Matroska,
+    /// Opus/Vorbis in particular) attach this per-packet rather than only
+    /// signalling padding once up front through the codec parameters.
+    pub fn skip_samples(&self) -> Option<(u32, u32)> {
+        let mut size: c_int = 0;
+
+        let data = unsafe { ffw_packet_get_side_data(self.ptr, AV_PKT_DATA_SKIP_SAMPLES, &mut size) };
+
+        if data.is_null() || size < 10 {
+            return None;
+        }
+
+        // Layout (little-endian), per FFmpeg's av_packet_side_data docs:
+        // u32 skip_start, u32 skip_end, u8 reason_start, u8 reason_end.
+        let bytes = unsafe { std::slice::from_raw_parts(data, 8) };
+
+        let skip_start = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let skip_end = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+
+        Some((skip_start, skip_end))
+    }
+}
+
+impl Clone for Packet {
+    fn clone(&self) -> Packet {
+        let ptr = unsafe { ffw_packet_clone(self.ptr) };
+
+        if ptr.is_null() {
+            panic!("unable to clone a Packet");
+        }
+
+        Packet { ptr }
+    }
+}
+
+impl Drop for Packet {
+    fn drop(&mut self) {
+        unsafe { ffw_packet_free(self.ptr) }
+    }
+}
+
+unsafe impl Send for Packet {}